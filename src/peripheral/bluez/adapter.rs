@@ -0,0 +1,97 @@
+use dbus::{
+    arg::{RefArg, Variant},
+    nonblock::stdintf::org_freedesktop_dbus::Properties,
+    Message, Path,
+};
+use futures::compat::*;
+use std::{collections::HashMap, sync::Arc};
+
+use super::{connection::Connection, constants::BLUEZ_SERVICE_NAME, error::Error};
+
+const ADAPTER_IFACE: &str = "org.bluez.Adapter1";
+
+#[derive(Debug)]
+pub struct Adapter {
+    connection: Arc<Connection>,
+    pub object_path: Path<'static>,
+}
+
+impl Adapter {
+    pub async fn new(connection: Arc<Connection>) -> Result<Self, Error> {
+        let object_path = Self::find_default_path(&connection).await?;
+        Ok(Adapter {
+            connection,
+            object_path,
+        })
+    }
+
+    /// Builds an `Adapter` bound to an already-resolved object path,
+    /// skipping the discovery that `new` performs, which takes the first
+    /// object exposing `org.bluez.Adapter1` in BlueZ's managed objects, in
+    /// no defined order.
+    pub async fn with_path(connection: Arc<Connection>, object_path: Path<'static>) -> Result<Self, Error> {
+        Ok(Adapter {
+            connection,
+            object_path,
+        })
+    }
+
+    async fn find_default_path(connection: &Connection) -> Result<Path<'static>, Error> {
+        let message = Message::new_method_call(
+            BLUEZ_SERVICE_NAME,
+            "/",
+            "org.freedesktop.DBus.ObjectManager",
+            "GetManagedObjects",
+        )
+        .map_err(Error::MessageConstruction)?;
+
+        let reply = connection
+            .default()
+            .method_call(message)
+            .map_err(|_| Error::Send)?
+            .compat()
+            .await
+            .map_err(Error::from)?;
+
+        let objects: HashMap<Path<'static>, HashMap<String, HashMap<String, Variant<Box<dyn RefArg>>>>> =
+            reply.read1().map_err(Error::from)?;
+
+        objects
+            .into_iter()
+            .find(|(_, ifaces)| ifaces.contains_key(ADAPTER_IFACE))
+            .map(|(path, _)| path)
+            .ok_or_else(|| Error::AdapterNotFound("default".to_owned()))
+    }
+
+    pub async fn powered(self: &Self, powered: bool) -> Result<(), Error> {
+        self.proxy()
+            .set(ADAPTER_IFACE, "Powered", powered)
+            .await
+            .map_err(Error::from)
+    }
+
+    pub async fn is_powered(self: &Self) -> Result<bool, Error> {
+        self.proxy()
+            .get(ADAPTER_IFACE, "Powered")
+            .await
+            .map_err(Error::from)
+    }
+
+    pub async fn get_alias(self: &Self) -> Result<String, Error> {
+        self.proxy()
+            .get(ADAPTER_IFACE, "Alias")
+            .await
+            .map_err(Error::from)
+    }
+
+    pub async fn set_alias(self: &Self, alias: &str) -> Result<(), Error> {
+        self.proxy()
+            .set(ADAPTER_IFACE, "Alias", alias.to_owned())
+            .await
+            .map_err(Error::from)
+    }
+
+    fn proxy(self: &Self) -> dbus::nonblock::Proxy<'static, Arc<dbus::nonblock::SyncConnection>> {
+        self.connection.get_bluez_proxy(&self.object_path)
+    }
+}
@@ -4,6 +4,7 @@ use dbus::{
     message::MatchRule,
     Path,
 };
+use futures::{channel::mpsc, stream::Stream};
 use std::{
     collections::HashMap,
     sync::{
@@ -19,6 +20,15 @@ use super::{
 };
 use crate::Error;
 
+/// State transitions pushed out of an [`Advertisement`] as BlueZ reports them.
+#[derive(Clone, Debug)]
+pub enum AdvertisingEvent {
+    /// BlueZ called `Release` on the advertisement object, e.g. because its
+    /// `Timeout` elapsed, the controller was reset, or the advertising
+    /// manager shut down.
+    Released,
+}
+
 #[derive(Clone, Debug)]
 struct ServiceData(HashMap<String, Vec<u8>>);
 
@@ -69,6 +79,56 @@ impl dbus::arg::Append for ServiceData {
     }
 }
 
+#[derive(Clone, Debug)]
+struct ManufacturerData(HashMap<u16, Vec<u8>>);
+
+impl ManufacturerData {
+    fn new() -> Self {
+        ManufacturerData(HashMap::new())
+    }
+}
+
+impl dbus::arg::Arg for ManufacturerData {
+    const ARG_TYPE: dbus::arg::ArgType = dbus::arg::ArgType::Array;
+
+    fn signature() -> dbus::Signature<'static> {
+        dbus::Signature::from("a{qv}")
+    }
+}
+
+impl dbus::arg::RefArg for ManufacturerData {
+    fn arg_type(&self) -> dbus::arg::ArgType {
+        <Self as dbus::arg::Arg>::ARG_TYPE
+    }
+
+    fn signature(&self) -> dbus::Signature<'static> {
+        <Self as dbus::arg::Arg>::signature()
+    }
+
+    fn append(&self, iter: &mut dbus::arg::IterAppend) {
+        <Self as dbus::arg::Append>::append_by_ref(self, iter);
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any where Self: 'static {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any where Self: 'static {
+        self
+    }
+}
+
+impl dbus::arg::Append for ManufacturerData {
+    fn append_by_ref(&self, iter: &mut dbus::arg::IterAppend) {
+        let mut to_append = HashMap::new();
+        for (k, v) in self.0.iter() {
+            let sliced: &[u8] = &*v;
+            to_append.insert(*k, Variant(sliced));
+        }
+        iter.append(to_append);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Advertisement {
     connection: Arc<Connection>,
@@ -79,28 +139,76 @@ pub struct Advertisement {
     name: Arc<Mutex<Option<String>>>,
     uuids: Arc<Mutex<Option<Vec<String>>>>,
     service_data: Arc<Mutex<Option<ServiceData>>>,
+    manufacturer_data: Arc<Mutex<Option<ManufacturerData>>>,
+    tx_power: Arc<Mutex<Option<bool>>>,
+    appearance: Arc<Mutex<Option<u16>>>,
+    discoverable: Arc<Mutex<Option<bool>>>,
+    timeout: Arc<Mutex<Option<u16>>>,
+    events: mpsc::UnboundedSender<AdvertisingEvent>,
+    events_receiver: Arc<Mutex<Option<mpsc::UnboundedReceiver<AdvertisingEvent>>>>,
 }
 
 impl Advertisement {
     pub fn new(connection: Arc<Connection>, adapter: Path<'static>) -> Self {
-        let mut tree = common::Tree::new();
         let is_advertising = Arc::new(AtomicBool::new(false));
-        let is_advertising_release = is_advertising.clone();
-
         let name = Arc::new(Mutex::new(None));
-        let name_property = name.clone();
-
         let uuids = Arc::new(Mutex::new(None));
-        let uuids_property = uuids.clone();
-
         let service_data = Arc::new(Mutex::new(None));
-        let service_data_property = service_data.clone();
-
+        let manufacturer_data = Arc::new(Mutex::new(None));
+        let tx_power = Arc::new(Mutex::new(None));
+        let appearance = Arc::new(Mutex::new(None));
+        let discoverable = Arc::new(Mutex::new(None));
+        let timeout = Arc::new(Mutex::new(None));
+        let (events, events_receiver) = mpsc::unbounded();
         let object_path: Path = format!("{}/advertisement{:04}", PATH_BASE, 0).into();
 
+        let advertisement = Advertisement {
+            connection,
+            adapter,
+            object_path,
+            tree: Arc::new(Mutex::new(common::Tree::new())),
+            is_advertising,
+            name,
+            uuids,
+            service_data,
+            manufacturer_data,
+            tx_power,
+            appearance,
+            discoverable,
+            timeout,
+            events,
+            events_receiver: Arc::new(Mutex::new(Some(events_receiver))),
+        };
+        advertisement.rebuild_tree();
+        advertisement.start_serving();
+        advertisement
+    }
+
+    /// (Re-)builds the `LEAdvertisement1` interface from the properties
+    /// currently set, and replaces the tree this advertisement serves with
+    /// it. `IncludeTxPower`, `Appearance`, `Discoverable` and `Timeout` are
+    /// only registered when they've actually been set: BlueZ applies its
+    /// own defaults for them, and a getter that errors when the value is
+    /// unset takes `Properties.GetAll` down with it instead of just
+    /// omitting that one property.
+    fn rebuild_tree(self: &Self) {
+        let mut tree = common::Tree::new();
+
+        let is_advertising_release = self.is_advertising.clone();
+        let events_release = self.events.clone();
+        let name_property = self.name.clone();
+        let uuids_property = self.uuids.clone();
+        let service_data_property = self.service_data.clone();
+        let manufacturer_data_property = self.manufacturer_data.clone();
+        let tx_power_value = *self.tx_power.lock().expect("Poisoned mutex");
+        let appearance_value = *self.appearance.lock().expect("Poisoned mutex");
+        let discoverable_value = *self.discoverable.lock().expect("Poisoned mutex");
+        let timeout_value = *self.timeout.lock().expect("Poisoned mutex");
+
         let iface_token = tree.register(LE_ADVERTISEMENT_IFACE, |b| {
             b.method_with_cr_async("Release", (), (), move |mut ctx, _cr, ()| {
                 is_advertising_release.store(false, Ordering::Relaxed);
+                let _ = events_release.unbounded_send(AdvertisingEvent::Released);
                 futures::future::ready(ctx.reply(Ok(())))
             });
             b.property("Type")
@@ -126,35 +234,69 @@ impl Advertisement {
                     .clone()
                     .unwrap_or_else(ServiceData::new))
             });
+            b.property("ManufacturerData").get(move |_ctx, _cr| {
+                Ok(manufacturer_data_property
+                    .lock()
+                    .expect("Poisoned mutex")
+                    .clone()
+                    .unwrap_or_else(ManufacturerData::new))
+            });
+            if let Some(tx_power) = tx_power_value {
+                b.property("IncludeTxPower")
+                    .get(move |_ctx, _cr| Ok(tx_power));
+            }
+            if let Some(appearance) = appearance_value {
+                b.property("Appearance")
+                    .get(move |_ctx, _cr| Ok(appearance));
+            }
+            if let Some(discoverable) = discoverable_value {
+                b.property("Discoverable")
+                    .get(move |_ctx, _cr| Ok(discoverable));
+            }
+            if let Some(timeout) = timeout_value {
+                b.property("Timeout").get(move |_ctx, _cr| Ok(timeout));
+            }
         });
         let ifaces = [iface_token, tree.object_manager()];
-        tree.insert(object_path.clone(), &ifaces, ());
-
-        let tree = Arc::new(Mutex::new(tree));
-
-        {
-            let tree = tree.clone();
-            let mut match_rule = MatchRule::new_method_call();
-            match_rule.path = Some(object_path.clone());
-            connection.default.start_receive(
-                match_rule,
-                Box::new(move |msg, conn| {
-                    tree.lock().unwrap().handle_message(msg, conn).unwrap();
-                    true
-                }),
-            );
-        }
+        tree.insert(self.object_path.clone(), &ifaces, ());
 
-        Advertisement {
-            connection,
-            adapter,
-            object_path,
-            tree,
-            is_advertising,
-            name,
-            uuids,
-            service_data,
-        }
+        *self.tree.lock().expect("Poisoned mutex") = tree;
+    }
+
+    /// Registers this advertisement's method-call receiver on the
+    /// connection it currently holds. Called once from `new`, and again
+    /// from `reattach` after `Connection::reconnect` hands out a new
+    /// underlying connection, since a `start_receive` handler is bound to
+    /// the specific connection it was registered on and does not follow
+    /// along when the connection is replaced.
+    fn start_serving(self: &Self) {
+        let tree = self.tree.clone();
+        let mut match_rule = MatchRule::new_method_call();
+        match_rule.path = Some(self.object_path.clone());
+        self.connection.default().start_receive(
+            match_rule,
+            Box::new(move |msg, conn| {
+                if tree.lock().unwrap().handle_message(msg, conn).is_err() {
+                    eprintln!("bluster: failed to dispatch message on advertisement tree");
+                }
+                true
+            }),
+        );
+    }
+
+    /// Re-attaches this advertisement's method-call receiver to the
+    /// connection's current system bus, after `Connection::reconnect` has
+    /// re-dialed it. Must be called before `register`, since BlueZ reads
+    /// the advertisement's properties as soon as `RegisterAdvertisement`
+    /// returns.
+    pub(crate) fn reattach(self: &Self) {
+        self.start_serving();
+    }
+
+    /// Returns a stream of [`AdvertisingEvent`]s reported by BlueZ, or
+    /// `None` if it has already been taken by an earlier call.
+    pub fn events(self: &Self) -> Option<impl Stream<Item = AdvertisingEvent>> {
+        self.events_receiver.lock().unwrap().take()
     }
 
     pub fn add_name<T: Into<String>>(self: &Self, name: T) {
@@ -174,13 +316,42 @@ impl Advertisement {
         let data = data.into();
         let mut guard = self.service_data.lock().unwrap();
         let m = guard.get_or_insert(ServiceData::new());
-        println!("here! {:?}", m);
         m.0.insert(uuid, data);
-        println!("there! {:?}", m);
+    }
+
+    /// Queues manufacturer-specific data for the advertisement. Like
+    /// [`set_tx_power`](Self::set_tx_power) and friends, and unlike
+    /// `ServiceData` (which is passed directly to `register`'s caller via
+    /// `Peripheral::start_advertising`), this is set out-of-band and only
+    /// takes effect if called before `register`.
+    pub fn add_manufacturer_data(self: &Self, company_id: u16, data: impl Into<Vec<u8>>) {
+        let data = data.into();
+        let mut guard = self.manufacturer_data.lock().unwrap();
+        let m = guard.get_or_insert(ManufacturerData::new());
+        m.0.insert(company_id, data);
+    }
+
+    pub fn set_tx_power(self: &Self, include: bool) {
+        self.tx_power.lock().unwrap().replace(include);
+    }
+
+    pub fn set_appearance(self: &Self, appearance: u16) {
+        self.appearance.lock().unwrap().replace(appearance);
+    }
+
+    pub fn set_discoverable(self: &Self, discoverable: bool) {
+        self.discoverable.lock().unwrap().replace(discoverable);
+    }
+
+    pub fn set_timeout(self: &Self, timeout: u16) {
+        self.timeout.lock().unwrap().replace(timeout);
     }
 
     pub async fn register(self: &Self) -> Result<(), Error> {
-        // Register with DBus
+        // Rebuild the tree so the properties advertised match whatever was
+        // set since the last call (or since `new`, the first time through).
+        self.rebuild_tree();
+
         let proxy = self.connection.get_bluez_proxy(&self.adapter);
         proxy
             .method_call(
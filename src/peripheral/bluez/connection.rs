@@ -0,0 +1,59 @@
+use dbus::{nonblock::SyncConnection, nonblock::Proxy, Path};
+use dbus_tokio::connection;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+use super::{constants::BLUEZ_SERVICE_NAME, error::Error};
+
+const METHOD_CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+struct Inner {
+    connection: Arc<SyncConnection>,
+    driver: JoinHandle<()>,
+}
+
+#[derive(Debug)]
+pub struct Connection {
+    inner: Mutex<Inner>,
+}
+
+impl Connection {
+    pub fn new() -> Result<Self, Error> {
+        Ok(Connection {
+            inner: Mutex::new(Self::dial()?),
+        })
+    }
+
+    fn dial() -> Result<Inner, Error> {
+        let (resource, connection) = connection::new_system_sync()?;
+        let driver = tokio::spawn(async move {
+            let err = resource.await;
+            eprintln!("bluster: lost connection to the system bus: {}", err);
+        });
+        Ok(Inner { connection, driver })
+    }
+
+    pub fn default(&self) -> Arc<SyncConnection> {
+        self.inner.lock().expect("Poisoned mutex").connection.clone()
+    }
+
+    pub fn get_bluez_proxy(&self, path: &Path<'static>) -> Proxy<'static, Arc<SyncConnection>> {
+        Proxy::new(BLUEZ_SERVICE_NAME, path.clone(), METHOD_CALL_TIMEOUT, self.default())
+    }
+
+    /// Tears down the current system-bus connection, if it is still alive,
+    /// and dials a fresh one. Callers are responsible for re-registering
+    /// anything that was bound to the old connection (GATT application,
+    /// advertisement, match rules) once this returns; see
+    /// `Peripheral::reconnect`.
+    pub async fn reconnect(&self) -> Result<(), Error> {
+        let fresh = Self::dial()?;
+        let previous = std::mem::replace(&mut *self.inner.lock().expect("Poisoned mutex"), fresh);
+        previous.driver.abort();
+        Ok(())
+    }
+}
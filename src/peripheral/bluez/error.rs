@@ -0,0 +1,46 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    Dbus(dbus::Error),
+    TypeMismatch(dbus::arg::TypeMismatchError),
+    /// A `dbus::Message` could not be constructed, e.g. an invalid object
+    /// path or interface name was supplied.
+    MessageConstruction(String),
+    /// The message was built successfully but could not be queued for
+    /// sending, e.g. the connection to the bus has gone away.
+    Send,
+    /// No adapter matching the requested path suffix or address was found
+    /// in BlueZ's managed objects.
+    AdapterNotFound(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Dbus(e) => write!(f, "D-Bus error: {}", e),
+            Error::TypeMismatch(e) => write!(f, "unexpected D-Bus reply shape: {}", e),
+            Error::MessageConstruction(reason) => {
+                write!(f, "failed to construct D-Bus message: {}", reason)
+            }
+            Error::Send => write!(f, "failed to send message on the D-Bus connection"),
+            Error::AdapterNotFound(adapter) => {
+                write!(f, "no BlueZ adapter matching `{}`", adapter)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<dbus::Error> for Error {
+    fn from(e: dbus::Error) -> Self {
+        Error::Dbus(e)
+    }
+}
+
+impl From<dbus::arg::TypeMismatchError> for Error {
+    fn from(e: dbus::arg::TypeMismatchError) -> Self {
+        Error::TypeMismatch(e)
+    }
+}
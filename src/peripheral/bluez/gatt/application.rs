@@ -50,16 +50,16 @@ impl Application {
             GATT_GATT_MANAGER_IFACE,
             "RegisterApplication",
         )
-        .unwrap()
+        .map_err(Error::MessageConstruction)?
         .append2(
             &self.object_path,
             HashMap::<String, Variant<Box<dyn RefArg>>>::new(),
         );
 
         self.connection
-            .default
+            .default()
             .method_call(message)
-            .unwrap()
+            .map_err(|_| Error::Send)?
             .compat()
             .await
             .map_err(Error::from)
@@ -72,13 +72,13 @@ impl Application {
             GATT_GATT_MANAGER_IFACE,
             "UnregisterApplication",
         )
-        .unwrap()
+        .map_err(Error::MessageConstruction)?
         .append1(&self.object_path);
 
         self.connection
-            .default
+            .default()
             .method_call(message)
-            .unwrap()
+            .map_err(|_| Error::Send)?
             .compat()
             .await
             .map(|_| ())
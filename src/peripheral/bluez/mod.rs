@@ -6,14 +6,24 @@ mod constants;
 mod error;
 mod gatt;
 
+use dbus::{
+    arg::{RefArg, Variant},
+    Message, Path,
+};
+use futures::{compat::*, stream::Stream};
 use std::{collections::HashMap, string::ToString, sync::Arc};
 use uuid::Uuid;
 
-use self::{adapter::Adapter, advertisement::Advertisement, connection::Connection, gatt::Gatt};
+pub use self::advertisement::AdvertisingEvent;
+use self::{
+    adapter::Adapter, advertisement::Advertisement, connection::Connection,
+    constants::BLUEZ_SERVICE_NAME, gatt::Gatt,
+};
 use crate::{gatt::service::Service, Error};
 
 #[derive(Debug)]
 pub struct Peripheral {
+    connection: Arc<Connection>,
     adapter: Adapter,
     gatt: Gatt,
     advertisement: Advertisement,
@@ -26,15 +36,87 @@ impl Peripheral {
         let adapter = Adapter::new(connection.clone()).await?;
         adapter.powered(true).await?;
         let gatt = Gatt::new(connection.clone(), adapter.object_path.clone());
-        let advertisement = Advertisement::new(connection, adapter.object_path.clone());
+        let advertisement = Advertisement::new(connection.clone(), adapter.object_path.clone());
 
         Ok(Peripheral {
+            connection,
             adapter,
             gatt,
             advertisement,
         })
     }
 
+    /// Builds a `Peripheral` bound to a specific BlueZ controller instead of
+    /// whichever adapter `new` happens to find first. `adapter` may be an
+    /// object-path suffix such as `hci1`, or a controller MAC address.
+    pub async fn with_adapter(adapter: &str) -> Result<Self, Error> {
+        let connection = Arc::new(Connection::new()?);
+        let adapter_path = Self::find_adapter_path(&connection, adapter).await?;
+        let adapter = Adapter::with_path(connection.clone(), adapter_path).await?;
+        adapter.powered(true).await?;
+        let gatt = Gatt::new(connection.clone(), adapter.object_path.clone());
+        let advertisement = Advertisement::new(connection.clone(), adapter.object_path.clone());
+
+        Ok(Peripheral {
+            connection,
+            adapter,
+            gatt,
+            advertisement,
+        })
+    }
+
+    /// Re-establishes the system-bus connection after a transient transport
+    /// failure (e.g. the bus daemon restarting), re-attaches the
+    /// advertisement's method-call receiver to the fresh connection, and
+    /// registers the GATT application again (whether or not it was
+    /// registered beforehand) and the advertisement if it was live
+    /// beforehand, instead of leaving the `Peripheral` permanently dead.
+    pub async fn reconnect(self: &Self) -> Result<(), Error> {
+        self.connection.reconnect().await?;
+        self.advertisement.reattach();
+        self.gatt.register().await?;
+        if self.advertisement.is_advertising() {
+            self.advertisement.register().await?;
+        }
+        Ok(())
+    }
+
+    async fn find_adapter_path(connection: &Connection, adapter: &str) -> Result<Path<'static>, Error> {
+        let message = Message::new_method_call(
+            BLUEZ_SERVICE_NAME,
+            "/",
+            "org.freedesktop.DBus.ObjectManager",
+            "GetManagedObjects",
+        )
+        .map_err(Error::MessageConstruction)?;
+
+        let reply = connection
+            .default()
+            .method_call(message)
+            .map_err(|_| Error::Send)?
+            .compat()
+            .await
+            .map_err(Error::from)?;
+
+        let objects: HashMap<Path<'static>, HashMap<String, HashMap<String, Variant<Box<dyn RefArg>>>>> =
+            reply.read1().map_err(Error::from)?;
+
+        objects
+            .into_iter()
+            .find(|(path, ifaces)| match ifaces.get("org.bluez.Adapter1") {
+                Some(props) => {
+                    (&**path).rsplit('/').next() == Some(adapter)
+                        || props
+                            .get("Address")
+                            .and_then(|address| address.0.as_str())
+                            .map_or(false, |address| address.eq_ignore_ascii_case(adapter))
+                }
+                None => false,
+            })
+            .map(|(path, _)| path)
+            .ok_or_else(|| Error::AdapterNotFound(adapter.to_owned()))
+    }
+
     pub async fn get_alias(&self) -> Result<String, Error> {
         self.adapter.get_alias().await
     }
@@ -64,7 +146,7 @@ impl Peripheral {
                 .map(ToString::to_string)
                 .collect::<Vec<String>>(),
         );
-        
+
         for (k,v) in data.into_iter() {
             self.advertisement.add_service_data(k.to_string(), v);
         }
@@ -72,6 +154,31 @@ impl Peripheral {
         self.advertisement.register().await
     }
 
+    /// Queues manufacturer-specific data for the advertisement. Must be
+    /// called before `start_advertising`, mirroring `set_tx_power`,
+    /// `set_appearance`, `set_discoverable` and `set_timeout` — unlike the
+    /// service data passed directly to `start_advertising`, this has no
+    /// effect if set afterwards.
+    pub fn add_manufacturer_data(self: &Self, company_id: u16, data: impl Into<Vec<u8>>) {
+        self.advertisement.add_manufacturer_data(company_id, data);
+    }
+
+    pub fn set_tx_power(self: &Self, include: bool) {
+        self.advertisement.set_tx_power(include);
+    }
+
+    pub fn set_appearance(self: &Self, appearance: u16) {
+        self.advertisement.set_appearance(appearance);
+    }
+
+    pub fn set_discoverable(self: &Self, discoverable: bool) {
+        self.advertisement.set_discoverable(discoverable);
+    }
+
+    pub fn set_timeout(self: &Self, timeout: u16) {
+        self.advertisement.set_timeout(timeout);
+    }
+
     pub async fn stop_advertising(self: &Self) -> Result<(), Error> {
         self.advertisement.unregister().await
     }
@@ -80,6 +187,13 @@ impl Peripheral {
         Ok(self.advertisement.is_advertising())
     }
 
+    /// Returns a stream of [`AdvertisingEvent`]s so callers can react to
+    /// BlueZ revoking advertising instead of polling `is_advertising`.
+    /// Returns `None` if called more than once.
+    pub fn advertising_events(self: &Self) -> Option<impl Stream<Item = AdvertisingEvent>> {
+        self.advertisement.events()
+    }
+
     pub fn add_service(self: &Self, service: &Service) -> Result<(), Error> {
         self.gatt.add_service(service)
     }